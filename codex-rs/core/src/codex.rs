@@ -254,7 +254,8 @@ pub struct CodexSpawnOk {
 }
 
 pub(crate) const INITIAL_SUBMIT_ID: &str = "";
-pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
+/// Submissions that can be queued for a session before `submit` waits.
+pub const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
 
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
@@ -1,18 +1,26 @@
 use crate::agent::AgentStatus;
 use crate::codex::Codex;
 use crate::codex::SteerInputError;
+use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::protocol::Event;
+use crate::protocol::EventMsg;
 use crate::protocol::Op;
 use crate::protocol::Submission;
+use codex_protocol::ThreadId;
 use codex_protocol::config_types::Personality;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::user_input::UserInput;
+use futures::Stream;
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
 
 use crate::state_db::StateDbHandle;
 
@@ -28,6 +36,16 @@ pub struct ThreadConfigSnapshot {
     pub session_source: SessionSource,
 }
 
+/// Coarse liveness of a [`CodexThread`], e.g. for a connection indicator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HealthStatus {
+    Healthy,
+    /// Still running, but new submissions will have to wait.
+    Degraded(String),
+    /// The session loop is gone; submissions and events will fail.
+    Unavailable(String),
+}
+
 pub struct CodexThread {
     codex: Codex,
     rollout_path: Option<PathBuf>,
@@ -47,6 +65,40 @@ impl CodexThread {
         self.codex.submit(op).await
     }
 
+    /// Like [`Self::submit`], but gives up with [`CodexErr::SubmitTimeout`] if
+    /// the agent has not accepted `op` within `timeout`. Submission waits on
+    /// the bounded submission queue, so a wedged session loop otherwise makes
+    /// this hang forever.
+    pub async fn submit_with_timeout(&self, op: Op, timeout: Duration) -> CodexResult<String> {
+        tokio::time::timeout(timeout, self.codex.submit(op))
+            .await
+            .map_err(|_| CodexErr::SubmitTimeout(timeout))?
+    }
+
+    /// Submit `ops` in order, returning their submission ids in the same order.
+    /// Stops at the first failure; later ops are not submitted.
+    pub async fn submit_batch(&self, ops: Vec<Op>) -> CodexResult<Vec<String>> {
+        let mut ids = Vec::with_capacity(ops.len());
+        for op in ops {
+            ids.push(self.submit(op).await?);
+        }
+        Ok(ids)
+    }
+
+    /// Ask the session to shut down, resolving once it reports
+    /// [`AgentStatus::Shutdown`]. This watches the agent status rather than the
+    /// event stream, so event consumers still receive `ShutdownComplete`.
+    pub async fn shutdown(&self) -> CodexResult<()> {
+        let mut status = self.subscribe_status();
+        self.submit(Op::Shutdown).await?;
+        // An error means the status sender is gone, i.e. the session has
+        // already torn down.
+        let _ = status
+            .wait_for(|status| matches!(status, AgentStatus::Shutdown))
+            .await;
+        Ok(())
+    }
+
     pub async fn steer_input(
         &self,
         input: Vec<UserInput>,
@@ -64,6 +116,56 @@ impl CodexThread {
         self.codex.next_event().await
     }
 
+    /// Like [`Self::next_event`], but returns `Ok(None)` once `token` is
+    /// cancelled, so a consumer can stop waiting without dropping its task.
+    pub async fn next_event_cancellable(
+        &self,
+        token: CancellationToken,
+    ) -> CodexResult<Option<Event>> {
+        tokio::select! {
+            biased;
+
+            _ = token.cancelled() => Ok(None),
+            event = self.codex.next_event() => event.map(Some),
+        }
+    }
+
+    /// This thread's events as a [`Stream`], for consumers that prefer stream
+    /// combinators over looping on [`Self::next_event`]. The stream ends after
+    /// yielding `ShutdownComplete`, or after yielding the first error, since the
+    /// event channel is gone at that point.
+    pub fn event_stream(self: Arc<Self>) -> Pin<Box<dyn Stream<Item = CodexResult<Event>> + Send>> {
+        Box::pin(futures::stream::unfold(Some(self), |thread| async move {
+            let thread = thread?;
+            let event = thread.next_event().await;
+            let done = match &event {
+                Ok(event) => matches!(event.msg, EventMsg::ShutdownComplete),
+                Err(_) => true,
+            };
+            Some((event, (!done).then_some(thread)))
+        }))
+    }
+
+    /// Stable id of this thread; matches the `session_id` carried by its
+    /// `SessionConfiguredEvent`.
+    pub fn thread_id(&self) -> ThreadId {
+        self.codex.session.conversation_id
+    }
+
+    /// Report whether this thread can still take submissions and emit events,
+    /// based on the state of its channels to the session loop.
+    pub fn health(&self) -> HealthStatus {
+        if self.codex.tx_sub.is_closed() {
+            HealthStatus::Unavailable("session loop stopped accepting submissions".to_string())
+        } else if self.codex.rx_event.is_closed() {
+            HealthStatus::Unavailable("session loop stopped emitting events".to_string())
+        } else if self.codex.tx_sub.is_full() {
+            HealthStatus::Degraded("submission queue is full".to_string())
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+
     pub async fn agent_status(&self) -> AgentStatus {
         self.codex.agent_status().await
     }
@@ -84,3 +186,265 @@ impl CodexThread {
         self.codex.thread_config_snapshot().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::protocol::WarningEvent;
+    use async_channel::Receiver;
+    use async_channel::Sender;
+    use futures::StreamExt;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::AtomicU64;
+
+    /// A thread backed by a real session but with test-owned channels, so tests
+    /// can play the session loop: read submissions from the returned receiver
+    /// and emit events through the returned sender. The submission queue holds
+    /// `submission_capacity` entries, and nothing drains it unless the test does.
+    async fn stub_thread(
+        submission_capacity: usize,
+    ) -> (CodexThread, Receiver<Submission>, Sender<Event>) {
+        let (thread, rx_sub, tx_event, _agent_status_tx) =
+            stub_thread_with_status(submission_capacity).await;
+        (thread, rx_sub, tx_event)
+    }
+
+    /// Like [`stub_thread`], but also returns the agent status sender.
+    async fn stub_thread_with_status(
+        submission_capacity: usize,
+    ) -> (
+        CodexThread,
+        Receiver<Submission>,
+        Sender<Event>,
+        watch::Sender<AgentStatus>,
+    ) {
+        let (session, _turn_context) = make_session_and_context().await;
+        let (tx_sub, rx_sub) = async_channel::bounded(submission_capacity);
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let (agent_status_tx, agent_status) = watch::channel(AgentStatus::PendingInit);
+        let codex = Codex {
+            next_id: AtomicU64::new(0),
+            tx_sub,
+            rx_event,
+            agent_status,
+            session: Arc::new(session),
+        };
+        (
+            CodexThread::new(codex, None),
+            rx_sub,
+            tx_event,
+            agent_status_tx,
+        )
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn submit_with_timeout_fails_when_submission_is_never_accepted() {
+        let (thread, _rx_sub, _tx_event) = stub_thread(1).await;
+        // Fill the queue; with nobody draining it, the next submit never completes.
+        thread.submit(Op::Interrupt).await.expect("first submit");
+
+        let timeout = Duration::from_secs(30);
+        let started = tokio::time::Instant::now();
+        let err = thread
+            .submit_with_timeout(Op::Interrupt, timeout)
+            .await
+            .expect_err("submit should time out");
+
+        assert!(
+            matches!(err, CodexErr::SubmitTimeout(after) if after == timeout),
+            "unexpected error: {err:?}"
+        );
+        assert_eq!(started.elapsed(), timeout);
+    }
+
+    #[tokio::test]
+    async fn submit_with_timeout_returns_id_when_accepted() {
+        let (thread, rx_sub, _tx_event) = stub_thread(1).await;
+
+        let id = thread
+            .submit_with_timeout(Op::Interrupt, Duration::from_secs(30))
+            .await
+            .expect("submit");
+
+        let submission = rx_sub.recv().await.expect("submission");
+        assert_eq!(submission.id, id);
+    }
+
+    #[tokio::test]
+    async fn next_event_cancellable_returns_none_once_cancelled() {
+        let (thread, _rx_sub, _tx_event) = stub_thread(1).await;
+        let thread = Arc::new(thread);
+        let token = CancellationToken::new();
+
+        // No event ever arrives, so the waiter can only finish via the token.
+        let waiter = tokio::spawn({
+            let thread = Arc::clone(&thread);
+            let token = token.clone();
+            async move { thread.next_event_cancellable(token).await }
+        });
+
+        token.cancel();
+        let event = tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("next_event_cancellable did not return after cancellation")
+            .expect("waiter task")
+            .expect("next_event_cancellable");
+        assert!(event.is_none());
+    }
+
+    #[tokio::test]
+    async fn next_event_cancellable_prefers_cancellation_over_pending_event() {
+        let (thread, _rx_sub, tx_event) = stub_thread(1).await;
+        tx_event
+            .send(Event {
+                id: "pending".to_string(),
+                msg: EventMsg::ShutdownComplete,
+            })
+            .await
+            .expect("send event");
+
+        let cancelled = CancellationToken::new();
+        cancelled.cancel();
+        let event = thread
+            .next_event_cancellable(cancelled)
+            .await
+            .expect("next_event_cancellable");
+        assert!(event.is_none());
+
+        // The pending event was left in place for the next caller.
+        let event = thread
+            .next_event_cancellable(CancellationToken::new())
+            .await
+            .expect("next_event_cancellable")
+            .expect("pending event");
+        assert_eq!(event.id, "pending");
+    }
+
+    fn warning(id: &str) -> Event {
+        Event {
+            id: id.to_string(),
+            msg: EventMsg::Warning(WarningEvent {
+                message: format!("warning {id}"),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn event_stream_ends_after_shutdown_complete() {
+        let (thread, _rx_sub, tx_event) = stub_thread(1).await;
+        for event in [
+            warning("1"),
+            warning("2"),
+            Event {
+                id: "3".to_string(),
+                msg: EventMsg::ShutdownComplete,
+            },
+            warning("after-shutdown"),
+        ] {
+            tx_event.send(event).await.expect("send event");
+        }
+
+        let ids: Vec<String> = Arc::new(thread)
+            .event_stream()
+            .map(|event| event.expect("event").id)
+            .collect()
+            .await;
+
+        assert_eq!(ids, vec!["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn event_stream_ends_after_error() {
+        let (thread, _rx_sub, tx_event) = stub_thread(1).await;
+        tx_event.send(warning("1")).await.expect("send event");
+        drop(tx_event);
+
+        let events: Vec<CodexResult<Event>> = Arc::new(thread).event_stream().collect().await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_ref().expect("event").id, "1");
+        assert!(matches!(events[1], Err(CodexErr::InternalAgentDied)));
+    }
+
+    #[tokio::test]
+    async fn submit_batch_returns_ids_in_submission_order() {
+        let (thread, rx_sub, _tx_event) = stub_thread(4).await;
+
+        let ids = thread
+            .submit_batch(vec![Op::Interrupt, Op::Compact, Op::Shutdown])
+            .await
+            .expect("submit batch");
+
+        let mut submitted = Vec::new();
+        while let Ok(submission) = rx_sub.try_recv() {
+            submitted.push((submission.id, submission.op));
+        }
+        assert_eq!(
+            submitted,
+            vec![
+                (ids[0].clone(), Op::Interrupt),
+                (ids[1].clone(), Op::Compact),
+                (ids[2].clone(), Op::Shutdown),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn health_reflects_session_loop_channels() {
+        let (thread, rx_sub, tx_event) = stub_thread(1).await;
+        assert_eq!(thread.health(), HealthStatus::Healthy);
+
+        thread.submit(Op::Interrupt).await.expect("submit");
+        assert_eq!(
+            thread.health(),
+            HealthStatus::Degraded("submission queue is full".to_string())
+        );
+
+        drop(tx_event);
+        assert_eq!(
+            thread.health(),
+            HealthStatus::Unavailable("session loop stopped emitting events".to_string())
+        );
+
+        drop(rx_sub);
+        assert_eq!(
+            thread.health(),
+            HealthStatus::Unavailable("session loop stopped accepting submissions".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn shutdown_submits_shutdown_and_waits_for_shutdown_status() {
+        let (thread, rx_sub, tx_event, agent_status_tx) = stub_thread_with_status(1).await;
+        let thread = Arc::new(thread);
+        let shutdown = tokio::spawn({
+            let thread = Arc::clone(&thread);
+            async move { thread.shutdown().await }
+        });
+
+        // Play the session loop: take the op, then report shutdown.
+        let submission = tokio::time::timeout(Duration::from_secs(1), rx_sub.recv())
+            .await
+            .expect("timed out waiting for submission")
+            .expect("submission");
+        assert_eq!(submission.op, Op::Shutdown);
+        agent_status_tx.send_replace(AgentStatus::Shutdown);
+        tx_event
+            .send(Event {
+                id: submission.id,
+                msg: EventMsg::ShutdownComplete,
+            })
+            .await
+            .expect("send event");
+
+        tokio::time::timeout(Duration::from_secs(1), shutdown)
+            .await
+            .expect("shutdown did not resolve")
+            .expect("shutdown task")
+            .expect("shutdown");
+        // Event consumers still observe ShutdownComplete.
+        let event = thread.next_event().await.expect("event");
+        assert!(matches!(event.msg, EventMsg::ShutdownComplete));
+    }
+}
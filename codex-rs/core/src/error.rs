@@ -88,6 +88,11 @@ pub enum CodexErr {
     #[error("timeout waiting for child process to exit")]
     Timeout,
 
+    /// Returned by `CodexThread::submit_with_timeout` when the agent did not
+    /// accept the submission in time, e.g. because its session loop is wedged.
+    #[error("timed out after {0:?} waiting for the agent to accept a submission")]
+    SubmitTimeout(Duration),
+
     /// Returned by run_command_stream when the child could not be spawned (its stdout/stderr pipes
     /// could not be captured). Analogous to the previous `CodexError::Spawn` variant.
     #[error("spawn failed: child stdout/stderr not captured")]
@@ -212,6 +217,7 @@ impl CodexErr {
             | CodexErr::ModelCap(_) => false,
             CodexErr::Stream(..)
             | CodexErr::Timeout
+            | CodexErr::SubmitTimeout(_)
             | CodexErr::UnexpectedStatus(_)
             | CodexErr::ResponseStreamFailed(_)
             | CodexErr::ConnectionFailed(_)
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_protocol::ThreadId;
 use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::TextElement;
 use codex_protocol::user_input::UserInput;
@@ -16,7 +17,9 @@ use core_test_support::skip_if_no_network;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
 use pretty_assertions::assert_eq;
+use serde_json::json;
 use std::sync::Arc;
+use tempfile::TempDir;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn resume_includes_initial_messages_from_rollout_events() -> Result<()> {
@@ -355,3 +358,48 @@ async fn resume_model_switch_is_not_duplicated_after_pre_turn_override() -> Resu
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn thread_id_matches_session_configured_across_resume() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let mut builder = test_codex();
+    let initial = builder.build(&server).await?;
+    assert_eq!(
+        initial.codex.thread_id(),
+        initial.session_configured.session_id
+    );
+
+    // Resume from a rollout written up front, so the check does not depend on
+    // the initial thread's rollout writer having flushed its session meta.
+    let rollout_dir = TempDir::new()?;
+    let rollout_path = rollout_dir.path().join("resume-session.jsonl");
+    let thread_id = ThreadId::new();
+    std::fs::write(
+        &rollout_path,
+        format!(
+            "{}\n",
+            json!({
+                "timestamp": "2024-01-01T00:00:00.000Z",
+                "type": "session_meta",
+                "payload": {
+                    "id": thread_id,
+                    "timestamp": "2024-01-01T00:00:00Z",
+                    "cwd": ".",
+                    "originator": "test_originator",
+                    "cli_version": "test_version",
+                    "model_provider": "test-provider"
+                }
+            })
+        ),
+    )?;
+
+    let resumed = builder
+        .resume(&server, initial.home.clone(), rollout_path)
+        .await?;
+    assert_eq!(resumed.session_configured.session_id, thread_id);
+    assert_eq!(resumed.codex.thread_id(), thread_id);
+
+    Ok(())
+}
@@ -143,6 +143,7 @@ use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Wrap;
 use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use tracing::debug;
 
@@ -484,6 +485,9 @@ pub(crate) enum ExternalEditorState {
 pub(crate) struct ChatWidget {
     app_event_tx: AppEventSender,
     codex_op_tx: UnboundedSender<Op>,
+    /// Pauses forwarding of this thread's events while `true`; `None` when the
+    /// event loop is owned elsewhere (see `new_with_op_sender`).
+    events_paused_tx: Option<watch::Sender<bool>>,
     bottom_pane: BottomPane,
     active_cell: Option<Box<dyn HistoryCell>>,
     /// Monotonic-ish counter used to invalidate transcript overlay caching.
@@ -2549,7 +2553,8 @@ impl ChatWidget {
         config.model = model.clone();
         let mut rng = rand::rng();
         let placeholder = PLACEHOLDERS[rng.random_range(0..PLACEHOLDERS.len())].to_string();
-        let codex_op_tx = spawn_agent(config.clone(), app_event_tx.clone(), thread_manager);
+        let (codex_op_tx, events_paused_tx) =
+            spawn_agent(config.clone(), app_event_tx.clone(), thread_manager);
 
         let model_override = model.as_deref();
         let model_for_header = model
@@ -2579,6 +2584,7 @@ impl ChatWidget {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
             codex_op_tx,
+            events_paused_tx: Some(events_paused_tx),
             bottom_pane: BottomPane::new(BottomPaneParams {
                 frame_requester,
                 app_event_tx,
@@ -2742,6 +2748,7 @@ impl ChatWidget {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
             codex_op_tx,
+            events_paused_tx: None,
             bottom_pane: BottomPane::new(BottomPaneParams {
                 frame_requester,
                 app_event_tx,
@@ -2876,7 +2883,7 @@ impl ChatWidget {
             .unwrap_or(header_model);
 
         let current_cwd = Some(session_configured.cwd.clone());
-        let codex_op_tx =
+        let (codex_op_tx, events_paused_tx) =
             spawn_agent_from_existing(conversation, session_configured, app_event_tx.clone());
 
         let fallback_default = Settings {
@@ -2894,6 +2901,7 @@ impl ChatWidget {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
             codex_op_tx,
+            events_paused_tx: Some(events_paused_tx),
             bottom_pane: BottomPane::new(BottomPaneParams {
                 frame_requester,
                 app_event_tx,
@@ -3393,6 +3401,9 @@ impl ChatWidget {
                     self.add_info_message("Rollout path is not available yet.".to_string(), None);
                 }
             }
+            SlashCommand::PauseEvents => {
+                self.toggle_events_paused();
+            }
             SlashCommand::TestApproval => {
                 use codex_core::protocol::EventMsg;
                 use std::collections::HashMap;
@@ -6849,6 +6860,27 @@ impl ChatWidget {
         self.current_rollout_path.clone()
     }
 
+    /// Toggle whether this thread's events are held back instead of rendered,
+    /// for freezing the transcript while debugging rendering. Held events are
+    /// rendered in order on resume.
+    fn toggle_events_paused(&mut self) {
+        let Some(events_paused_tx) = &self.events_paused_tx else {
+            self.add_info_message(
+                "Pausing events is not available for this thread.".to_string(),
+                None,
+            );
+            return;
+        };
+        let paused = !*events_paused_tx.borrow();
+        events_paused_tx.send_replace(paused);
+        let message = if paused {
+            "Incoming events paused; run /pause-events again to resume."
+        } else {
+            "Incoming events resumed."
+        };
+        self.add_info_message(message.to_string(), None);
+    }
+
     /// Returns a cache key describing the current in-flight active cell for the transcript overlay.
     ///
     /// `Ctrl+T` renders committed transcript cells plus a render-only live tail derived from the
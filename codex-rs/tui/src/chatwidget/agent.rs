@@ -1,26 +1,116 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use codex_core::CodexThread;
 use codex_core::NewThread;
 use codex_core::ThreadManager;
+use codex_core::codex::SUBMISSION_CHANNEL_CAPACITY;
 use codex_core::config::Config;
+use codex_core::error::Result as CodexResult;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::SessionConfiguredEvent;
+use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::mpsc::unbounded_channel;
+use tokio::sync::oneshot;
+use tokio::sync::watch;
+use tokio::time::Instant;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::history_cell;
 
 /// Spawn the agent bootstrapper and op forwarding loop, returning the
-/// `UnboundedSender<Op>` used by the UI to submit operations.
+/// `UnboundedSender<Op>` used by the UI to submit operations and the pause
+/// control for the thread's event stream (see [`AgentLoopOptions::events_paused`]).
 pub(crate) fn spawn_agent(
     config: Config,
     app_event_tx: AppEventSender,
     server: Arc<ThreadManager>,
-) -> UnboundedSender<Op> {
-    let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<Op>();
+) -> (UnboundedSender<Op>, watch::Sender<bool>) {
+    let (events_paused_tx, events_paused) = watch::channel(false);
+    let options = AgentLoopOptions {
+        events_paused,
+        ..AgentLoopOptions::default()
+    };
+    let (codex_op_tx, _session_configured_rx) =
+        spawn_agent_with_handle(config, app_event_tx, server, options);
+    (codex_op_tx, events_paused_tx)
+}
+
+/// Settings for the op and event loops started for a thread.
+pub(crate) struct AgentLoopOptions {
+    /// While this holds `true`, thread events are buffered instead of being
+    /// forwarded to the UI, and are flushed in order once it flips back.
+    pub(crate) events_paused: watch::Receiver<bool>,
+    /// When set, caps how many ops may wait for submission. Defaults to
+    /// [`DEFAULT_OP_QUEUE_LIMITS`].
+    pub(crate) op_queue_limits: Option<OpQueueLimits>,
+    /// When set, consecutive op submissions start at least this far apart, so
+    /// bursts are spread out rather than sent all at once.
+    pub(crate) op_min_interval: Option<Duration>,
+    /// When set, a running turn that goes this long without any event from the
+    /// thread is interrupted, and the user is told why.
+    pub(crate) turn_idle_timeout: Option<Duration>,
+}
+
+impl Default for AgentLoopOptions {
+    fn default() -> Self {
+        Self {
+            events_paused: never_paused(),
+            op_queue_limits: Some(DEFAULT_OP_QUEUE_LIMITS),
+            op_min_interval: None,
+            turn_idle_timeout: None,
+        }
+    }
+}
+
+/// Like [`spawn_agent`], but also returns a receiver that resolves with the
+/// thread's `SessionConfiguredEvent` as soon as the thread has started, so the
+/// caller can read the session id without waiting for the event to flow
+/// through the app event queue. The receiver errors if the thread fails to
+/// start.
+pub(crate) fn spawn_agent_with_handle(
+    config: Config,
+    app_event_tx: AppEventSender,
+    server: Arc<ThreadManager>,
+    options: AgentLoopOptions,
+) -> (
+    UnboundedSender<Op>,
+    oneshot::Receiver<SessionConfiguredEvent>,
+) {
+    spawn_agent_loops(
+        async move { server.start_thread(config).await },
+        app_event_tx,
+        options,
+    )
+}
+
+/// Start a thread via `start_thread` on a background task, then run the op and
+/// event loops for it. If starting fails, the error is shown, a fatal exit is
+/// requested and the returned receiver errors.
+fn spawn_agent_loops<S>(
+    start_thread: S,
+    app_event_tx: AppEventSender,
+    options: AgentLoopOptions,
+) -> (
+    UnboundedSender<Op>,
+    oneshot::Receiver<SessionConfiguredEvent>,
+)
+where
+    S: Future<Output = CodexResult<NewThread>> + Send + 'static,
+{
+    let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+    let (session_configured_tx, session_configured_rx) = oneshot::channel();
+    let turn_idle_interrupt = TurnIdleInterrupt::new(options.turn_idle_timeout, &codex_op_tx);
 
     let app_event_tx_clone = app_event_tx;
     tokio::spawn(async move {
@@ -28,7 +118,7 @@ pub(crate) fn spawn_agent(
             thread,
             session_configured,
             ..
-        } = match server.start_thread(config).await {
+        } = match start_thread.await {
             Ok(v) => v,
             Err(err) => {
                 let message = format!("Failed to initialize codex: {err}");
@@ -43,92 +133,981 @@ pub(crate) fn spawn_agent(
             }
         };
 
-        // Forward the captured `SessionConfigured` event so it can be rendered in the UI.
-        let ev = codex_core::protocol::Event {
-            // The `id` does not matter for rendering, so we can use a fake value.
-            id: "".to_string(),
-            msg: codex_core::protocol::EventMsg::SessionConfigured(session_configured),
-        };
-        app_event_tx_clone.send(AppEvent::CodexEvent(ev));
-
-        let thread_clone = thread.clone();
-        tokio::spawn(async move {
-            while let Some(op) = codex_op_rx.recv().await {
-                let id = thread_clone.submit(op).await;
-                if let Err(e) = id {
-                    tracing::error!("failed to submit op: {e}");
-                }
-            }
-        });
+        announce_session_configured(
+            session_configured,
+            Some(session_configured_tx),
+            &app_event_tx_clone,
+        );
 
-        while let Ok(event) = thread.next_event().await {
-            let is_shutdown_complete = matches!(event.msg, EventMsg::ShutdownComplete);
-            app_event_tx_clone.send(AppEvent::CodexEvent(event));
-            if is_shutdown_complete {
-                // ShutdownComplete is terminal for a thread; drop this receiver task so
-                // the Arc<CodexThread> can be released and thread resources can clean up.
-                break;
-            }
-        }
+        spawn_thread_op_loop(
+            thread.clone(),
+            codex_op_rx,
+            app_event_tx_clone.clone(),
+            options.op_queue_limits,
+            options.op_min_interval,
+        );
+
+        forward_events(
+            thread_events(thread),
+            app_event_tx_clone,
+            options.events_paused,
+            turn_idle_interrupt,
+        )
+        .await;
     });
 
-    codex_op_tx
+    (codex_op_tx, session_configured_rx)
 }
 
 /// Spawn agent loops for an existing thread (e.g., a forked thread).
 /// Sends the provided `SessionConfiguredEvent` immediately, then forwards subsequent
-/// events and accepts Ops for submission.
+/// events and accepts Ops for submission. Returns the op sender and the pause
+/// control for the event stream, like [`spawn_agent`].
 pub(crate) fn spawn_agent_from_existing(
     thread: std::sync::Arc<CodexThread>,
     session_configured: codex_core::protocol::SessionConfiguredEvent,
     app_event_tx: AppEventSender,
-) -> UnboundedSender<Op> {
-    let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<Op>();
+) -> (UnboundedSender<Op>, watch::Sender<bool>) {
+    let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+    let (events_paused_tx, events_paused) = watch::channel(false);
+    let options = AgentLoopOptions {
+        events_paused,
+        ..AgentLoopOptions::default()
+    };
+    let turn_idle_interrupt = TurnIdleInterrupt::new(options.turn_idle_timeout, &codex_op_tx);
 
     let app_event_tx_clone = app_event_tx;
     tokio::spawn(async move {
-        // Forward the captured `SessionConfigured` event so it can be rendered in the UI.
-        let ev = codex_core::protocol::Event {
-            id: "".to_string(),
-            msg: codex_core::protocol::EventMsg::SessionConfigured(session_configured),
-        };
-        app_event_tx_clone.send(AppEvent::CodexEvent(ev));
-
-        let thread_clone = thread.clone();
-        tokio::spawn(async move {
-            while let Some(op) = codex_op_rx.recv().await {
-                let id = thread_clone.submit(op).await;
-                if let Err(e) = id {
-                    tracing::error!("failed to submit op: {e}");
+        announce_session_configured(session_configured, None, &app_event_tx_clone);
+
+        spawn_thread_op_loop(
+            thread.clone(),
+            codex_op_rx,
+            app_event_tx_clone.clone(),
+            options.op_queue_limits,
+            options.op_min_interval,
+        );
+
+        forward_events(
+            thread_events(thread),
+            app_event_tx_clone,
+            options.events_paused,
+            turn_idle_interrupt,
+        )
+        .await;
+    });
+
+    (codex_op_tx, events_paused_tx)
+}
+
+/// Boxed future yielding a thread's next event.
+type NextEventFuture = Pin<Box<dyn Future<Output = CodexResult<Event>> + Send>>;
+
+/// `next_event` source for [`forward_events`] that owns `thread`, so the
+/// thread is released as soon as the event loop is done with it.
+fn thread_events(thread: Arc<CodexThread>) -> impl FnMut() -> NextEventFuture {
+    move || -> NextEventFuture {
+        let thread = thread.clone();
+        Box::pin(async move { thread.next_event().await })
+    }
+}
+
+/// Spawn the loop that submits UI ops to `thread`.
+fn spawn_thread_op_loop(
+    thread: Arc<CodexThread>,
+    codex_op_rx: UnboundedReceiver<Op>,
+    app_event_tx: AppEventSender,
+    op_queue_limits: Option<OpQueueLimits>,
+    op_min_interval: Option<Duration>,
+) {
+    spawn_op_submit_loop(
+        codex_op_rx,
+        app_event_tx,
+        op_queue_limits,
+        op_min_interval,
+        move |op| {
+            let thread = thread.clone();
+            async move { thread.submit(op).await }
+        },
+    );
+}
+
+/// Interrupts a running turn that has gone quiet; see
+/// [`AgentLoopOptions::turn_idle_timeout`].
+struct TurnIdleInterrupt {
+    after: Duration,
+    codex_op_tx: UnboundedSender<Op>,
+}
+
+impl TurnIdleInterrupt {
+    fn new(after: Option<Duration>, codex_op_tx: &UnboundedSender<Op>) -> Option<Self> {
+        after.map(|after| Self {
+            after,
+            codex_op_tx: codex_op_tx.clone(),
+        })
+    }
+}
+
+/// Forward thread events to the UI until `ShutdownComplete` or the thread goes
+/// away.
+///
+/// While `events_paused` holds `true`, events are held back in arrival order
+/// and flushed once it flips back to `false`, so pausing never drops or
+/// reorders events; events that arrive just before shutdown are kept until
+/// resume too. Dropping the pause sender counts as a permanent resume.
+/// `next_event` must be cancel-safe, since it is raced against pause changes;
+/// it is dropped as soon as the thread is done, before waiting to flush held
+/// events, so whatever it owns is not kept alive by a paused UI.
+///
+/// With `turn_idle_interrupt`, a turn that produces no event for its `after`
+/// duration gets an error cell and an `Op::Interrupt`. The deadline starts at
+/// `TurnStarted`, moves with every event and is cleared when the turn ends.
+async fn forward_events<F, Fut>(
+    mut next_event: F,
+    app_event_tx: AppEventSender,
+    mut events_paused: watch::Receiver<bool>,
+    turn_idle_interrupt: Option<TurnIdleInterrupt>,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = CodexResult<Event>>,
+{
+    let mut paused = *events_paused.borrow_and_update();
+    let mut pause_control_open = true;
+    let mut held: VecDeque<Event> = VecDeque::new();
+    let mut turn_deadline: Option<Instant> = None;
+
+    loop {
+        tokio::select! {
+            biased;
+
+            changed = events_paused.changed(), if pause_control_open => {
+                if changed.is_ok() {
+                    paused = *events_paused.borrow_and_update();
+                } else {
+                    pause_control_open = false;
+                    paused = false;
+                }
+                if !paused {
+                    for event in held.drain(..) {
+                        app_event_tx.send(AppEvent::CodexEvent(event));
+                    }
                 }
             }
-        });
-
-        while let Ok(event) = thread.next_event().await {
-            let is_shutdown_complete = matches!(event.msg, EventMsg::ShutdownComplete);
-            app_event_tx_clone.send(AppEvent::CodexEvent(event));
-            if is_shutdown_complete {
-                // ShutdownComplete is terminal for a thread; drop this receiver task so
-                // the Arc<CodexThread> can be released and thread resources can clean up.
-                break;
+            event = next_event() => {
+                let Ok(event) = event else {
+                    break;
+                };
+                let is_shutdown_complete = matches!(event.msg, EventMsg::ShutdownComplete);
+                if let Some(idle) = &turn_idle_interrupt {
+                    turn_deadline = match event.msg {
+                        EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_) => None,
+                        EventMsg::TurnStarted(_) => Some(Instant::now() + idle.after),
+                        _ => turn_deadline.map(|_| Instant::now() + idle.after),
+                    };
+                }
+                if paused {
+                    held.push_back(event);
+                } else {
+                    app_event_tx.send(AppEvent::CodexEvent(event));
+                }
+                if is_shutdown_complete {
+                    // ShutdownComplete is terminal for a thread; stop receiving so the
+                    // Arc<CodexThread> can be released and thread resources can clean up.
+                    break;
+                }
+            }
+            _ = tokio::time::sleep_until(turn_deadline.unwrap_or_else(Instant::now)),
+                if turn_deadline.is_some() =>
+            {
+                turn_deadline = None;
+                if let Some(idle) = &turn_idle_interrupt {
+                    let message = format!(
+                        "No activity from Codex for {:?}; interrupting the turn.",
+                        idle.after
+                    );
+                    tracing::warn!("{message}");
+                    app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                        history_cell::new_error_event(message),
+                    )));
+                    let _ = idle.codex_op_tx.send(Op::Interrupt);
+                }
             }
         }
-    });
+    }
 
-    codex_op_tx
+    drop(next_event);
+    if !held.is_empty() {
+        // An error here means the pause sender is gone; flush regardless.
+        let _ = events_paused.wait_for(|paused| !*paused).await;
+        for event in held {
+            app_event_tx.send(AppEvent::CodexEvent(event));
+        }
+    }
 }
 
-/// Spawn an op-forwarding loop for an existing thread without subscribing to events.
-pub(crate) fn spawn_op_forwarder(thread: std::sync::Arc<CodexThread>) -> UnboundedSender<Op> {
-    let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<Op>();
+/// Pause control for loops whose events are never paused.
+fn never_paused() -> watch::Receiver<bool> {
+    watch::channel(false).1
+}
+
+/// Hand the captured `SessionConfiguredEvent` to the caller waiting on it (if
+/// any), then forward it to the UI so it can be rendered.
+fn announce_session_configured(
+    session_configured: SessionConfiguredEvent,
+    session_configured_tx: Option<oneshot::Sender<SessionConfiguredEvent>>,
+    app_event_tx: &AppEventSender,
+) {
+    if let Some(tx) = session_configured_tx {
+        // The caller may have dropped the receiver; that is fine.
+        let _ = tx.send(session_configured.clone());
+    }
+
+    let ev = Event {
+        // The `id` does not matter for rendering, so we can use a fake value.
+        id: "".to_string(),
+        msg: EventMsg::SessionConfigured(session_configured),
+    };
+    app_event_tx.send(AppEvent::CodexEvent(ev));
+}
+
+/// Spawn the loop that submits UI ops via `submit`. Failures are logged and
+/// also surfaced in the transcript, so the user can tell that an action did not
+/// go through (e.g. because the backend has gone away). The loop keeps going
+/// after a failure. With `op_queue_limits`, ops waiting behind a slow submit
+/// are bounded as described in [`bound_op_queue`], and with `min_interval`
+/// consecutive submits start at least that far apart; ops that arrive sooner
+/// wait their turn instead of being dropped.
+fn spawn_op_submit_loop<F, Fut>(
+    codex_op_rx: UnboundedReceiver<Op>,
+    app_event_tx: AppEventSender,
+    op_queue_limits: Option<OpQueueLimits>,
+    min_interval: Option<Duration>,
+    submit: F,
+) where
+    F: Fn(Op) -> Fut + Send + 'static,
+    Fut: Future<Output = CodexResult<String>> + Send,
+{
+    match op_queue_limits {
+        Some(limits) => {
+            let (codex_op_rx, submit) =
+                bound_op_queue(codex_op_rx, app_event_tx.clone(), limits, submit);
+            tokio::spawn(run_op_submit_loop(
+                codex_op_rx,
+                app_event_tx,
+                min_interval,
+                submit,
+            ));
+        }
+        None => {
+            tokio::spawn(run_op_submit_loop(
+                codex_op_rx,
+                app_event_tx,
+                min_interval,
+                submit,
+            ));
+        }
+    }
+}
+
+async fn run_op_submit_loop<F, Fut>(
+    mut codex_op_rx: UnboundedReceiver<Op>,
+    app_event_tx: AppEventSender,
+    min_interval: Option<Duration>,
+    submit: F,
+) where
+    F: Fn(Op) -> Fut,
+    Fut: Future<Output = CodexResult<String>>,
+{
+    let mut next_submit_at: Option<Instant> = None;
+    while let Some(op) = codex_op_rx.recv().await {
+        if let Some(at) = next_submit_at {
+            tokio::time::sleep_until(at).await;
+        }
+        next_submit_at = min_interval.map(|interval| Instant::now() + interval);
+        let Err(e) = submit(op).await else {
+            continue;
+        };
+        tracing::error!("failed to submit op: {e}");
+        app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+            history_cell::new_error_event(format!("Failed to submit to codex: {e}")),
+        )));
+    }
+}
+
+/// Bound on how many UI ops may wait for submission, so a stalled backend shows
+/// up in the UI instead of as an ever-growing op queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OpQueueLimits {
+    /// Ops arriving while this many are already waiting are rejected.
+    pub(crate) depth: usize,
+    /// Once this many ops are waiting, a "backend busy" warning is shown. It is
+    /// shown again only after the queue has drained below this mark.
+    pub(crate) watermark: usize,
+}
 
+/// Limits used by the agent loops: as many waiting ops as core itself queues
+/// before `submit` blocks, with a warning once a quarter of that is reached.
+pub(crate) const DEFAULT_OP_QUEUE_LIMITS: OpQueueLimits = OpQueueLimits {
+    depth: SUBMISSION_CHANNEL_CAPACITY,
+    watermark: SUBMISSION_CHANNEL_CAPACITY / 4,
+};
+
+/// Put `limits` in front of `submit`. Ops from `codex_op_rx` are relayed to the
+/// returned receiver and counted until the returned submit function takes
+/// them; reaching the watermark inserts a warning into the transcript, and an
+/// op that would exceed the depth is dropped with an error cell rather than
+/// buffered.
+fn bound_op_queue<F, Fut>(
+    mut codex_op_rx: UnboundedReceiver<Op>,
+    app_event_tx: AppEventSender,
+    limits: OpQueueLimits,
+    submit: F,
+) -> (UnboundedReceiver<Op>, impl Fn(Op) -> Fut + Send + 'static)
+where
+    F: Fn(Op) -> Fut + Send + 'static,
+    Fut: Future<Output = CodexResult<String>> + Send,
+{
+    let (queued_tx, queued_rx) = unbounded_channel::<Op>();
+    let waiting = Arc::new(AtomicUsize::new(0));
+    let warned = Arc::new(AtomicBool::new(false));
+
+    let intake_waiting = waiting.clone();
+    let intake_warned = warned.clone();
     tokio::spawn(async move {
         while let Some(op) = codex_op_rx.recv().await {
-            if let Err(e) = thread.submit(op).await {
-                tracing::error!("failed to submit op: {e}");
+            let queued = intake_waiting.load(Ordering::SeqCst);
+            if queued >= limits.depth {
+                tracing::warn!("op queue full ({queued} waiting); rejecting op");
+                app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    history_cell::new_error_event(format!(
+                        "Codex is not keeping up ({queued} actions waiting), so this action was not sent. Try again once it catches up."
+                    )),
+                )));
+                continue;
+            }
+            // Count before handing the op over, so the submit side never sees
+            // an op it has to uncount before it was counted.
+            let queued = intake_waiting.fetch_add(1, Ordering::SeqCst) + 1;
+            if queued_tx.send(op).is_err() {
+                // The submit loop has stopped; nothing will drain the queue.
+                break;
+            }
+            if queued >= limits.watermark && !intake_warned.swap(true, Ordering::SeqCst) {
+                app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    history_cell::new_warning_event(format!(
+                        "Codex is busy: {queued} actions are waiting to be sent."
+                    )),
+                )));
             }
         }
     });
 
+    let submit = move |op| {
+        let queued = waiting.fetch_sub(1, Ordering::SeqCst) - 1;
+        if queued < limits.watermark {
+            warned.store(false, Ordering::SeqCst);
+        }
+        submit(op)
+    };
+    (queued_rx, submit)
+}
+
+/// Spawn an op-forwarding loop for an existing thread without subscribing to events.
+pub(crate) fn spawn_op_forwarder(
+    thread: std::sync::Arc<CodexThread>,
+    app_event_tx: AppEventSender,
+) -> UnboundedSender<Op> {
+    spawn_op_sender(app_event_tx, move |op| {
+        let thread = thread.clone();
+        async move { thread.submit(op).await }
+    })
+}
+
+/// Create an op channel whose ops are submitted via `submit` on a background
+/// task, with failures reported to the UI like the other agent loops.
+fn spawn_op_sender<F, Fut>(app_event_tx: AppEventSender, submit: F) -> UnboundedSender<Op>
+where
+    F: Fn(Op) -> Fut + Send + 'static,
+    Fut: Future<Output = CodexResult<String>> + Send,
+{
+    let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+    spawn_op_submit_loop(codex_op_rx, app_event_tx, None, None, submit);
     codex_op_tx
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history_cell::HistoryCell;
+    use codex_core::error::CodexErr;
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_core::protocol::AskForApproval;
+    use codex_core::protocol::SandboxPolicy;
+    use codex_core::protocol::TurnCompleteEvent;
+    use codex_core::protocol::TurnStartedEvent;
+    use codex_protocol::ThreadId;
+    use codex_protocol::config_types::ModeKind;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    /// Wait for the next app event and return the text of the history cell it inserts.
+    async fn next_history_cell_text(rx: &mut UnboundedReceiver<AppEvent>) -> String {
+        let event = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("timed out waiting for app event")
+            .expect("app event channel closed");
+        let AppEvent::InsertHistoryCell(cell) = event else {
+            panic!("expected a history cell");
+        };
+        cell.display_lines(80)
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .map(|span| span.content.as_ref())
+            .collect::<String>()
+    }
+
+    #[tokio::test]
+    async fn op_submit_failure_is_reported_to_ui() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+        spawn_op_submit_loop(
+            codex_op_rx,
+            AppEventSender::new(tx_raw),
+            None,
+            None,
+            |_op| async { Err(CodexErr::InternalAgentDied) },
+        );
+
+        codex_op_tx.send(Op::Interrupt).expect("send op");
+
+        let rendered = next_history_cell_text(&mut rx).await;
+        assert!(
+            rendered.contains("Failed to submit to codex"),
+            "unexpected error cell: {rendered}"
+        );
+    }
+
+    #[tokio::test]
+    async fn op_submit_loop_keeps_submitting_after_failure() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let submit_calls = calls.clone();
+        spawn_op_submit_loop(
+            codex_op_rx,
+            AppEventSender::new(tx_raw),
+            None,
+            None,
+            move |_op| {
+                let call = submit_calls.fetch_add(1, Ordering::SeqCst) + 1;
+                async move {
+                    if call == 2 {
+                        Err(CodexErr::InternalAgentDied)
+                    } else {
+                        Ok(call.to_string())
+                    }
+                }
+            },
+        );
+
+        for _ in 0..3 {
+            codex_op_tx.send(Op::Interrupt).expect("send op");
+        }
+        let rendered = next_history_cell_text(&mut rx).await;
+        assert!(
+            rendered.contains("Failed to submit to codex"),
+            "unexpected error cell: {rendered}"
+        );
+        drop(codex_op_tx);
+
+        // The loop drops the app event sender once it exits.
+        let remaining = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("timed out waiting for the op loop to exit");
+        assert!(remaining.is_none(), "unexpected extra app event");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn op_forwarder_failure_is_reported_to_ui() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let codex_op_tx = spawn_op_sender(AppEventSender::new(tx_raw), |_op| async {
+            Err(CodexErr::InternalAgentDied)
+        });
+
+        codex_op_tx.send(Op::Interrupt).expect("send op");
+
+        let rendered = next_history_cell_text(&mut rx).await;
+        assert!(
+            rendered.contains("Failed to submit to codex"),
+            "unexpected error cell: {rendered}"
+        );
+    }
+
+    #[tokio::test]
+    async fn full_op_queue_warns_at_watermark_and_rejects_overflow() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+        let limits = OpQueueLimits {
+            depth: 3,
+            watermark: 2,
+        };
+        // Nothing drains the queue until the test calls `submit`, like a stalled backend.
+        let (mut queued_rx, submit) = bound_op_queue(
+            codex_op_rx,
+            AppEventSender::new(tx_raw),
+            limits,
+            |_op| async { Ok("0".to_string()) },
+        );
+
+        for _ in 0..4 {
+            codex_op_tx.send(Op::Interrupt).expect("send op");
+        }
+
+        let warning = next_history_cell_text(&mut rx).await;
+        assert!(
+            warning.contains("Codex is busy: 2 actions are waiting"),
+            "unexpected warning cell: {warning}"
+        );
+        let rejection = next_history_cell_text(&mut rx).await;
+        assert!(
+            rejection.contains("3 actions waiting"),
+            "unexpected rejection cell: {rejection}"
+        );
+
+        // Only ops within the depth were queued.
+        let mut queued = Vec::new();
+        while let Ok(op) = queued_rx.try_recv() {
+            queued.push(op);
+        }
+        assert_eq!(queued.len(), 3);
+
+        // Draining below the watermark re-arms the warning for the next fill.
+        for op in queued {
+            submit(op).await.expect("submit");
+        }
+        codex_op_tx.send(Op::Interrupt).expect("send op");
+        codex_op_tx.send(Op::Interrupt).expect("send op");
+        let warning = next_history_cell_text(&mut rx).await;
+        assert!(
+            warning.contains("Codex is busy: 2 actions are waiting"),
+            "unexpected warning cell: {warning}"
+        );
+    }
+
+    #[tokio::test]
+    async fn op_submit_loop_warns_and_rejects_while_submit_is_stalled() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+        let (started_tx, mut started_rx) = unbounded_channel::<usize>();
+        let calls = Arc::new(AtomicUsize::new(0));
+        // Submits hang until the test hands out permits, like a stalled backend.
+        let gate = Arc::new(tokio::sync::Semaphore::new(0));
+        let submit_calls = calls.clone();
+        let submit_gate = gate.clone();
+        spawn_op_submit_loop(
+            codex_op_rx,
+            AppEventSender::new(tx_raw),
+            Some(OpQueueLimits {
+                depth: 3,
+                watermark: 2,
+            }),
+            None,
+            move |_op| {
+                let call = submit_calls.fetch_add(1, Ordering::SeqCst) + 1;
+                let _ = started_tx.send(call);
+                let gate = submit_gate.clone();
+                async move {
+                    gate.acquire().await.expect("gate").forget();
+                    Ok(call.to_string())
+                }
+            },
+        );
+
+        // The first op is taken straight away and parks in submit.
+        codex_op_tx.send(Op::Interrupt).expect("send op");
+        let started = tokio::time::timeout(Duration::from_secs(1), started_rx.recv())
+            .await
+            .expect("timed out waiting for first submit");
+        assert_eq!(started, Some(1));
+
+        // Three more fill the queue; a fourth is one too many.
+        for _ in 0..4 {
+            codex_op_tx.send(Op::Interrupt).expect("send op");
+        }
+        let warning = next_history_cell_text(&mut rx).await;
+        assert!(
+            warning.contains("Codex is busy: 2 actions are waiting"),
+            "unexpected warning cell: {warning}"
+        );
+        let rejection = next_history_cell_text(&mut rx).await;
+        assert!(
+            rejection.contains("3 actions waiting"),
+            "unexpected rejection cell: {rejection}"
+        );
+
+        // Once the backend recovers, every queued op is submitted.
+        gate.add_permits(4);
+        for expected in 2..=4 {
+            let started = tokio::time::timeout(Duration::from_secs(1), started_rx.recv())
+                .await
+                .expect("timed out waiting for queued submit");
+            assert_eq!(started, Some(expected));
+        }
+        drop(codex_op_tx);
+        let remaining = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("timed out waiting for the op loop to exit");
+        assert!(remaining.is_none(), "unexpected extra app event");
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn op_submit_loop_spaces_out_bursts_by_min_interval() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+        let (submitted_tx, mut submitted_rx) = unbounded_channel::<Instant>();
+        let min_interval = Duration::from_millis(500);
+        spawn_op_submit_loop(
+            codex_op_rx,
+            AppEventSender::new(tx_raw),
+            None,
+            Some(min_interval),
+            move |_op| {
+                let _ = submitted_tx.send(Instant::now());
+                async { Ok("0".to_string()) }
+            },
+        );
+
+        for _ in 0..3 {
+            codex_op_tx.send(Op::Interrupt).expect("send op");
+        }
+        drop(codex_op_tx);
+
+        let mut submitted = Vec::new();
+        while let Some(at) = submitted_rx.recv().await {
+            submitted.push(at);
+        }
+        // Nothing is dropped, and each submit waits out the interval.
+        assert_eq!(submitted.len(), 3);
+        for pair in submitted.windows(2) {
+            assert!(
+                pair[1] - pair[0] >= min_interval,
+                "submits only {:?} apart",
+                pair[1] - pair[0]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn op_submit_success_does_not_report() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+        spawn_op_submit_loop(
+            codex_op_rx,
+            AppEventSender::new(tx_raw),
+            None,
+            None,
+            |_op| async { Ok("0".to_string()) },
+        );
+
+        codex_op_tx.send(Op::Interrupt).expect("send op");
+        drop(codex_op_tx);
+
+        // The loop exits once the op channel closes; nothing should have been reported.
+        assert!(rx.recv().await.is_none());
+    }
+
+    fn agent_message(id: &str) -> Event {
+        Event {
+            id: id.to_string(),
+            msg: EventMsg::AgentMessage(AgentMessageEvent {
+                message: format!("message {id}"),
+            }),
+        }
+    }
+
+    /// Stub `next_event` source fed from a channel; errors once the channel closes.
+    fn stub_events(events_rx: UnboundedReceiver<Event>) -> impl FnMut() -> NextEventFuture {
+        let events_rx = Arc::new(tokio::sync::Mutex::new(events_rx));
+        move || -> NextEventFuture {
+            let events_rx = events_rx.clone();
+            Box::pin(async move {
+                events_rx
+                    .lock()
+                    .await
+                    .recv()
+                    .await
+                    .ok_or(CodexErr::InternalAgentDied)
+            })
+        }
+    }
+
+    async fn next_codex_event_id(rx: &mut UnboundedReceiver<AppEvent>) -> String {
+        let event = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("timed out waiting for app event")
+            .expect("app event channel closed");
+        let AppEvent::CodexEvent(event) = event else {
+            panic!("expected a codex event");
+        };
+        event.id
+    }
+
+    #[tokio::test]
+    async fn paused_events_are_delivered_in_order_after_resume() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (events_tx, events_rx) = unbounded_channel::<Event>();
+        let (pause_tx, pause_rx) = watch::channel(false);
+        let forwarder = tokio::spawn(forward_events(
+            stub_events(events_rx),
+            AppEventSender::new(tx_raw),
+            pause_rx,
+            None,
+        ));
+
+        events_tx.send(agent_message("1")).expect("send event");
+        assert_eq!(next_codex_event_id(&mut rx).await, "1");
+
+        pause_tx.send(true).expect("pause");
+        events_tx.send(agent_message("2")).expect("send event");
+        events_tx.send(agent_message("3")).expect("send event");
+        events_tx
+            .send(Event {
+                id: "4".to_string(),
+                msg: EventMsg::ShutdownComplete,
+            })
+            .expect("send event");
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), rx.recv())
+                .await
+                .is_err(),
+            "events were forwarded while paused"
+        );
+
+        pause_tx.send(false).expect("resume");
+        let mut delivered = Vec::new();
+        for _ in 0..3 {
+            delivered.push(next_codex_event_id(&mut rx).await);
+        }
+        assert_eq!(delivered, vec!["2", "3", "4"]);
+        forwarder.await.expect("forwarder task");
+    }
+
+    fn turn_started() -> Event {
+        Event {
+            id: "turn".to_string(),
+            msg: EventMsg::TurnStarted(TurnStartedEvent {
+                model_context_window: None,
+                collaboration_mode_kind: ModeKind::Default,
+            }),
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn quiet_turn_is_interrupted_after_idle_timeout() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (events_tx, events_rx) = unbounded_channel::<Event>();
+        let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<Op>();
+        let after = Duration::from_millis(500);
+        let _forwarder = tokio::spawn(forward_events(
+            stub_events(events_rx),
+            AppEventSender::new(tx_raw),
+            never_paused(),
+            TurnIdleInterrupt::new(Some(after), &codex_op_tx),
+        ));
+
+        let started = Instant::now();
+        events_tx.send(turn_started()).expect("send event");
+        assert_eq!(next_codex_event_id(&mut rx).await, "turn");
+
+        // The turn then goes quiet.
+        let rendered = next_history_cell_text(&mut rx).await;
+        assert!(
+            rendered.contains("interrupting the turn"),
+            "unexpected error cell: {rendered}"
+        );
+        let op = tokio::time::timeout(Duration::from_secs(1), codex_op_rx.recv())
+            .await
+            .expect("timed out waiting for interrupt")
+            .expect("op channel closed");
+        assert_eq!(op, Op::Interrupt);
+        assert!(started.elapsed() >= after);
+
+        // Only one interrupt per quiet spell.
+        tokio::time::sleep(after * 4).await;
+        assert!(
+            codex_op_rx.try_recv().is_err(),
+            "interrupted more than once"
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn finished_turn_is_not_interrupted() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (events_tx, events_rx) = unbounded_channel::<Event>();
+        let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<Op>();
+        let after = Duration::from_millis(500);
+        let _forwarder = tokio::spawn(forward_events(
+            stub_events(events_rx),
+            AppEventSender::new(tx_raw),
+            never_paused(),
+            TurnIdleInterrupt::new(Some(after), &codex_op_tx),
+        ));
+
+        events_tx.send(turn_started()).expect("send event");
+        events_tx
+            .send(Event {
+                id: "done".to_string(),
+                msg: EventMsg::TurnComplete(TurnCompleteEvent {
+                    last_agent_message: None,
+                }),
+            })
+            .expect("send event");
+        assert_eq!(next_codex_event_id(&mut rx).await, "turn");
+        assert_eq!(next_codex_event_id(&mut rx).await, "done");
+
+        tokio::time::sleep(after * 4).await;
+        assert!(
+            codex_op_rx.try_recv().is_err(),
+            "idle session was interrupted"
+        );
+        assert!(rx.try_recv().is_err(), "unexpected app event");
+    }
+
+    /// Signals on `drop`, to observe when a captured value is released.
+    struct DropSignal(Option<oneshot::Sender<()>>);
+
+    impl Drop for DropSignal {
+        fn drop(&mut self) {
+            if let Some(tx) = self.0.take() {
+                let _ = tx.send(());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn shutdown_while_paused_releases_event_source_without_resume() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (events_tx, events_rx) = unbounded_channel::<Event>();
+        let (pause_tx, pause_rx) = watch::channel(true);
+        let (released_tx, released_rx) = oneshot::channel();
+        let signal = DropSignal(Some(released_tx));
+        let mut next_event = stub_events(events_rx);
+        let _forwarder = tokio::spawn(forward_events(
+            move || {
+                let _owned = &signal;
+                next_event()
+            },
+            AppEventSender::new(tx_raw),
+            pause_rx,
+            None,
+        ));
+
+        events_tx
+            .send(Event {
+                id: "shutdown".to_string(),
+                msg: EventMsg::ShutdownComplete,
+            })
+            .expect("send event");
+
+        // The event source (standing in for the thread) is released even though
+        // the UI never resumes.
+        tokio::time::timeout(Duration::from_secs(1), released_rx)
+            .await
+            .expect("event source was not released while paused")
+            .expect("drop signal");
+        assert!(rx.try_recv().is_err(), "events were forwarded while paused");
+        assert!(*pause_tx.borrow());
+    }
+
+    fn session_configured_event() -> SessionConfiguredEvent {
+        SessionConfiguredEvent {
+            session_id: ThreadId::new(),
+            forked_from_id: None,
+            thread_name: None,
+            model: "test-model".to_string(),
+            model_provider_id: "test-provider".to_string(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            cwd: PathBuf::from("/home/user/project"),
+            reasoning_effort: None,
+            history_log_id: 0,
+            history_entry_count: 0,
+            initial_messages: None,
+            rollout_path: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn session_configured_receiver_errors_when_thread_fails_to_start() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (_codex_op_tx, session_configured_rx) = spawn_agent_loops(
+            async { Err(CodexErr::InternalAgentDied) },
+            AppEventSender::new(tx_raw),
+            AgentLoopOptions::default(),
+        );
+
+        let resolved = tokio::time::timeout(Duration::from_secs(1), session_configured_rx)
+            .await
+            .expect("timed out waiting for session configured");
+        assert!(resolved.is_err(), "receiver resolved despite failed start");
+
+        let event = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("timed out waiting for error event")
+            .expect("app event channel closed");
+        assert!(
+            matches!(
+                event,
+                AppEvent::CodexEvent(Event {
+                    msg: EventMsg::Error(_),
+                    ..
+                })
+            ),
+            "expected an error event"
+        );
+        let event = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("timed out waiting for fatal exit request")
+            .expect("app event channel closed");
+        assert!(
+            matches!(event, AppEvent::FatalExitRequest(_)),
+            "expected a fatal exit request"
+        );
+    }
+
+    #[tokio::test]
+    async fn announce_session_configured_resolves_handle_and_forwards_event() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let (session_configured_tx, session_configured_rx) = oneshot::channel();
+        let configured = session_configured_event();
+        let session_id = configured.session_id;
+
+        announce_session_configured(
+            configured,
+            Some(session_configured_tx),
+            &AppEventSender::new(tx_raw),
+        );
+
+        let resolved = tokio::time::timeout(Duration::from_secs(1), session_configured_rx)
+            .await
+            .expect("timed out waiting for session configured")
+            .expect("session configured sender dropped");
+        assert_eq!(resolved.session_id, session_id);
+
+        // The same event is still forwarded to the UI for rendering.
+        let event = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("timed out waiting for forwarded event")
+            .expect("app event channel closed");
+        let AppEvent::CodexEvent(Event {
+            msg: EventMsg::SessionConfigured(forwarded),
+            ..
+        }) = event
+        else {
+            panic!("expected a forwarded SessionConfigured event");
+        };
+        assert_eq!(forwarded.session_id, session_id);
+    }
+}
@@ -1043,6 +1043,7 @@ async fn make_chatwidget_manual(
     let mut widget = ChatWidget {
         app_event_tx,
         codex_op_tx: op_tx,
+        events_paused_tx: None,
         bottom_pane: bottom,
         active_cell: None,
         active_cell_revision: 0,
@@ -3269,6 +3270,33 @@ async fn slash_rollout_handles_missing_path() {
     );
 }
 
+#[tokio::test]
+async fn slash_pause_events_toggles_event_pause() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let (events_paused_tx, events_paused_rx) = tokio::sync::watch::channel(false);
+    chat.events_paused_tx = Some(events_paused_tx);
+
+    chat.dispatch_command(SlashCommand::PauseEvents);
+    assert!(*events_paused_rx.borrow());
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected info message for pause");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("paused"),
+        "expected pause message: {rendered}"
+    );
+
+    chat.dispatch_command(SlashCommand::PauseEvents);
+    assert!(!*events_paused_rx.borrow());
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected info message for resume");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("resumed"),
+        "expected resume message: {rendered}"
+    );
+}
+
 #[tokio::test]
 async fn undo_success_events_render_info_messages() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
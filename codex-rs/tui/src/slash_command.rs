@@ -42,6 +42,7 @@ pub enum SlashCommand {
     Exit,
     Feedback,
     Rollout,
+    PauseEvents,
     Ps,
     Personality,
     TestApproval,
@@ -81,6 +82,7 @@ impl SlashCommand {
             SlashCommand::Apps => "manage apps",
             SlashCommand::Logout => "log out of Codex",
             SlashCommand::Rollout => "print the rollout file path",
+            SlashCommand::PauseEvents => "pause or resume rendering of incoming events",
             SlashCommand::TestApproval => "test approval request",
         }
     }
@@ -130,6 +132,7 @@ impl SlashCommand {
             | SlashCommand::Quit
             | SlashCommand::Exit => true,
             SlashCommand::Rollout => true,
+            SlashCommand::PauseEvents => true,
             SlashCommand::TestApproval => true,
             SlashCommand::Collab => true,
             SlashCommand::Agent => true,
@@ -139,7 +142,9 @@ impl SlashCommand {
 
     fn is_visible(self) -> bool {
         match self {
-            SlashCommand::Rollout | SlashCommand::TestApproval => cfg!(debug_assertions),
+            SlashCommand::Rollout | SlashCommand::PauseEvents | SlashCommand::TestApproval => {
+                cfg!(debug_assertions)
+            }
             _ => true,
         }
     }